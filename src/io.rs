@@ -20,6 +20,9 @@ use std::{fs, io, path};
 
 use tempfile::NamedTempFile;
 
+#[cfg(unix)]
+use std::sync::Once;
+
 /// Reads the contents of a file line by line using buffered I/O.
 ///
 /// This function opens the file at the specified path and returns an iterator over its lines,
@@ -104,6 +107,111 @@ pub fn try_new_tempfile(content: impl AsRef<str>) -> io::Result<NamedTempFile> {
     Ok(file)
 }
 
+/// Creates a new, randomly-named file inside `dir` and writes the given content into it.
+///
+/// Unlike [`try_new_tempfile`], which delegates naming to `tempfile`, this generates its
+/// own 32-character alphanumeric filename so that many uniquely-named files can share one
+/// caller-controlled scratch directory (see [`try_new_scratch_dir`]) instead of being
+/// scattered across the system temp directory. On a name collision it retries with a
+/// freshly generated name, reusing the same existence-check loop as [`crate::rand::generate_badfile`].
+///
+/// # Parameters
+/// - `dir`: The directory in which to create the file. Accepts any type implementing `AsRef<Path>`.
+/// - `content`: The string content to write into the newly created file. Accepts any type implementing `AsRef<str>`.
+///
+/// # Returns
+/// - An `Result` containing the created file's full path and its `File` handle, or an
+///   error if file creation or writing fails.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing;
+///
+/// let (path, _file) = regd_testing::io::try_new_named_tempfile_in(".", "Hello, world!")
+///     .expect("failed to create named temp file");
+/// println!("Created {:?}", path);
+/// ```
+pub fn try_new_named_tempfile_in(
+    dir: impl AsRef<path::Path>,
+    content: impl AsRef<str>,
+) -> io::Result<(path::PathBuf, fs::File)> {
+    loop {
+        let name = crate::rand::generate_alphanumeric(32);
+        let candidate = dir.as_ref().join(name);
+        if fs::metadata(&candidate).is_ok() {
+            continue;
+        }
+        let file = try_new_file(&candidate, content.as_ref())?;
+        return Ok((candidate, file));
+    }
+}
+
+/// An RAII guard for a randomly-named scratch directory.
+///
+/// The directory is created on construction via [`try_new_scratch_dir`] and recursively
+/// removed, with the same retry-on-failure behavior as [`try_remove_file`], when the guard
+/// is dropped. This gives callers a single place to drop many generations of scratch files
+/// (e.g. via [`try_new_named_tempfile_in`]) and have them all cleaned up together.
+pub struct ScratchDir {
+    path: path::PathBuf,
+}
+
+impl ScratchDir {
+    /// Returns the path of the scratch directory.
+    pub fn path(&self) -> &path::Path {
+        &self.path
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        for attempt in 1..=4 {
+            match fs::remove_dir_all(&self.path) {
+                Ok(_) => return,
+                Err(_) if attempt < 4 => continue,
+                Err(e) => {
+                    eprintln!(
+                        "regd_testing::io: failed to remove scratch dir {:?}: {e}",
+                        self.path
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Creates a new, randomly-named scratch directory.
+///
+/// The directory is created under the system's default temporary directory and removed
+/// recursively when the returned [`ScratchDir`] guard is dropped, so many files created
+/// within it (e.g. via [`try_new_named_tempfile_in`]) are cleaned up together.
+///
+/// # Returns
+/// - An `Result` containing a [`ScratchDir`] guard if the directory was created
+///   successfully, or an error if it could not be.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing;
+///
+/// let scratch = regd_testing::io::try_new_scratch_dir().expect("failed to create scratch dir");
+/// let (path, _file) = regd_testing::io::try_new_named_tempfile_in(scratch.path(), "data")
+///     .expect("failed to create file in scratch dir");
+/// println!("Wrote {:?}", path);
+/// // `scratch` is removed, along with everything inside it, when it goes out of scope.
+/// ```
+pub fn try_new_scratch_dir() -> io::Result<ScratchDir> {
+    loop {
+        let name = crate::rand::generate_alphanumeric(32);
+        let path = std::env::temp_dir().join(name);
+        if fs::metadata(&path).is_ok() {
+            continue;
+        }
+        fs::create_dir(&path)?;
+        return Ok(ScratchDir { path });
+    }
+}
+
 /// Attempts to remove a file at the specified path, retrying up to 4 times on failure.
 ///
 /// This function tries to delete the file located at the given path. If the removal
@@ -140,3 +248,140 @@ pub fn try_remove_file(path: impl AsRef<path::Path>) -> io::Result<()> {
     }
     Ok(())
 }
+
+/// Raises the process's soft limit on open file descriptors as high as it is allowed to go.
+///
+/// On Unix, this queries `RLIMIT_NOFILE` via `getrlimit`, raises the soft limit to match
+/// the hard limit, and applies it with `setrlimit`. On macOS the hard limit reported by
+/// `getrlimit` is often `RLIM_INFINITY`, which the kernel rejects, so the target is capped
+/// by `kern.maxfilesperproc` from `sysctl` instead. On Windows, the analogous limit is the
+/// C runtime's stdio table size, raised via `_setmaxstdio`. On any other platform there is
+/// no portable way to query or raise this limit, so this always returns `0`.
+///
+/// Tests and harnesses that spawn many child processes or open many files at once
+/// (the classic `EMFILE` problem) should call this once at startup, e.g. via
+/// [`raise_fd_limit_once`].
+///
+/// # Returns
+/// - The new soft limit on success.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing;
+///
+/// let limit = regd_testing::io::raise_fd_limit().expect("failed to raise fd limit");
+/// println!("Open file limit is now {}", limit);
+/// ```
+#[cfg(unix)]
+pub fn raise_fd_limit() -> io::Result<u64> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut target = limit.rlim_max;
+    #[cfg(target_os = "macos")]
+    {
+        target = target.min(darwin_open_max());
+    }
+
+    limit.rlim_cur = target;
+    if unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(limit.rlim_cur as u64)
+}
+
+/// Windows' stdio table has no hard ceiling analogous to `RLIMIT_NOFILE`'s hard limit, so
+/// this raises the soft limit to a fixed, generously high target instead of querying one.
+const WINDOWS_MAX_STDIO: libc::c_int = 8192;
+
+/// Raises the C runtime's stdio table size via `_setmaxstdio`, the closest Windows
+/// equivalent of a raisable open file descriptor limit.
+///
+/// # Returns
+/// - The new limit on success.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing;
+///
+/// let limit = regd_testing::io::raise_fd_limit().expect("failed to raise fd limit");
+/// println!("Open file limit is now {}", limit);
+/// ```
+#[cfg(windows)]
+pub fn raise_fd_limit() -> io::Result<u64> {
+    let raised = unsafe { libc::setmaxstdio(WINDOWS_MAX_STDIO) };
+    if raised > 0 {
+        return Ok(raised as u64);
+    }
+
+    let current = unsafe { libc::getmaxstdio() };
+    if current < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(current as u64)
+}
+
+/// Fallback for platforms that are neither Unix nor Windows, which always reports `0`.
+///
+/// There is no portable API on these platforms to query or raise the open file
+/// descriptor limit, so this does not reflect "whatever is already in effect" — it is a
+/// constant placeholder. Callers gating concurrency on the returned value should not treat
+/// `0` as a real capacity on these targets.
+///
+/// # Returns
+/// - Always `Ok(0)`.
+#[cfg(not(any(unix, windows)))]
+pub fn raise_fd_limit() -> io::Result<u64> {
+    Ok(0)
+}
+
+/// Reads the `kern.maxfilesperproc` sysctl, which caps how high `RLIMIT_NOFILE` can
+/// practically be raised on Darwin even when `getrlimit` reports `rlim_max` as infinite.
+#[cfg(target_os = "macos")]
+fn darwin_open_max() -> libc::rlim_t {
+    let mut value: libc::c_int = 0;
+    let mut size = std::mem::size_of::<libc::c_int>();
+    let name = std::ffi::CString::new("kern.maxfilesperproc").expect("valid sysctl name");
+    let rc = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            &mut value as *mut _ as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if rc == 0 && value > 0 {
+        value as libc::rlim_t
+    } else {
+        libc::OPEN_MAX as libc::rlim_t
+    }
+}
+
+/// Raises the open file descriptor limit exactly once per process.
+///
+/// Subsequent calls are no-ops, so test harnesses can call this unconditionally from
+/// shared setup code without repeatedly touching `RLIMIT_NOFILE`. Failures are reported
+/// to stderr rather than propagated, since a harness's own setup should not fail merely
+/// because the platform declined to raise the limit.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing;
+///
+/// regd_testing::io::raise_fd_limit_once();
+/// ```
+#[cfg(unix)]
+pub fn raise_fd_limit_once() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        if let Err(e) = raise_fd_limit() {
+            eprintln!("regd_testing::io: failed to raise fd limit: {e}");
+        }
+    });
+}
@@ -14,12 +14,125 @@
 
 //! This module contains a set of testing utilities of random value generators.
 
+use std::cell::RefCell;
 use std::fs;
+use std::panic;
 
 use rand::Rng;
 use rand::distr::{Alphanumeric, StandardUniform};
 use rand::distr::uniform::{SampleRange, SampleUniform};
 use rand::prelude::Distribution;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+thread_local! {
+    /// The thread-local seeded generator. `None` means "fall back to `rand::rng()`", which
+    /// is the default, unseeded behavior.
+    static SEEDED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Expands a 64-bit seed into the 32-byte seed array `StdRng` requires, using splitmix64.
+fn expand_seed(seed: u64) -> [u8; 32] {
+    let mut state = seed;
+    let mut expanded = [0u8; 32];
+    for chunk in expanded.chunks_mut(8) {
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        chunk.copy_from_slice(&z.to_le_bytes());
+    }
+    expanded
+}
+
+/// Runs `f` against whichever generator is currently active: the seeded thread-local
+/// `StdRng` if one has been set via [`seed`] or [`with_seed`], or `rand::rng()` otherwise.
+fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    SEEDED_RNG.with(|cell| {
+        let mut seeded = cell.borrow_mut();
+        if let Some(rng) = seeded.as_mut() {
+            f(rng)
+        } else {
+            drop(seeded);
+            f(&mut rand::rng())
+        }
+    })
+}
+
+/// Pins the thread-local random number generator to a deterministic seed.
+///
+/// Every call to a function in this module (and every [`crate::slice_ext::SliceExt`]
+/// method) made on the current thread will subsequently draw from a seeded `StdRng`
+/// instead of `rand::rng()`, making the sequence of generated values reproducible.
+///
+/// # Parameters
+/// - `seed`: The 64-bit seed to expand into the generator's internal state.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// regd_testing::rand::seed(42);
+/// let x: u32 = regd_testing::rand::generate();
+/// println!("Reproducible value: {}", x);
+/// ```
+pub fn seed(seed: u64) {
+    SEEDED_RNG.with(|cell| {
+        *cell.borrow_mut() = Some(StdRng::from_seed(expand_seed(seed)));
+    });
+}
+
+/// Runs `f` with the thread-local generator pinned to `seed`, restoring whatever
+/// generator state was active beforehand once `f` returns.
+///
+/// This is the scoped counterpart to [`seed`]: it lets a single test or fuzz run opt
+/// into a deterministic sequence without leaking that determinism to code that runs
+/// after it. If `f` panics, the active seed is printed to stderr before the panic
+/// continues to unwind, so a CI failure can be replayed with `with_seed(seed, ...)`.
+///
+/// # Parameters
+/// - `seed`: The 64-bit seed to pin for the duration of `f`.
+/// - `f`: The closure to run under the seeded generator.
+///
+/// # Returns
+/// - Whatever `f` returns.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::with_seed(42, || regd_testing::rand::generate::<u32>());
+/// let y = regd_testing::rand::with_seed(42, || regd_testing::rand::generate::<u32>());
+/// assert_eq!(x, y);
+/// ```
+///
+/// # Panics
+/// - Propagates any panic raised by `f`, after printing the active seed to stderr.
+pub fn with_seed<F, R>(seed: u64, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Guard(Option<StdRng>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            SEEDED_RNG.with(|cell| *cell.borrow_mut() = self.0.take());
+        }
+    }
+
+    let previous =
+        SEEDED_RNG.with(|cell| cell.borrow_mut().replace(StdRng::from_seed(expand_seed(seed))));
+    let _guard = Guard(previous);
+
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(payload) => {
+            eprintln!("regd_testing::rand: panicked while seeded with {seed}");
+            panic::resume_unwind(payload);
+        }
+    }
+}
 
 /// Generates a random value of type `T`.
 ///
@@ -43,8 +156,7 @@ pub fn generate<T>() -> T
 where
     StandardUniform: Distribution<T>,
 {
-    let mut rng = rand::rng();
-    rng.random::<T>()
+    with_rng(|rng| rng.random::<T>())
 }
 
 /// Generates a random value of type `T` within the specified range.
@@ -77,8 +189,7 @@ where
     R: SampleRange<T>,
 {
     assert!(!range.is_empty(), "cannot sample empty range");
-    let mut rng = rand::rng();
-    rng.random_range(range)
+    with_rng(|rng| rng.random_range(range))
 }
 
 /// Generates a vector of random bytes of the specified length.
@@ -101,8 +212,7 @@ where
 /// println!("Random bytes: {:?}", x);
 /// ```
 pub fn generate_bytes(length: usize) -> Vec<u8> {
-    let mut rng = rand::rng();
-    (0..length).map(|_| rng.random::<u8>()).collect()
+    with_rng(|rng| (0..length).map(|_| rng.random::<u8>()).collect())
 }
 
 /// Generates a random alphanumeric string of the specified length.
@@ -125,11 +235,11 @@ pub fn generate_bytes(length: usize) -> Vec<u8> {
 /// assert_eq!(x.len(), 12);
 /// ```
 pub fn generate_alphanumeric(length: usize) -> String {
-    let rng = rand::rng();
-    rng.sample_iter(&Alphanumeric)
-        .take(length)
-        .map(char::from)
-        .collect()
+    with_rng(|rng| {
+        (0..length)
+            .map(|_| char::from(Alphanumeric.sample(rng)))
+            .collect()
+    })
 }
 
 /// Generates a random alphanumeric filename that does not exist in the current directory.
@@ -164,14 +274,87 @@ pub fn generate_alphanumeric(length: usize) -> String {
 pub fn generate_badfile(length: usize) -> String {
     assert!(length > 0, "cannot sample empty file name");
     loop {
-        let rng = rand::rng();
-        let filename: String = rng
-            .sample_iter(&Alphanumeric)
-            .take(length)
-            .map(char::from)
-            .collect();
+        let filename: String = with_rng(|rng| {
+            (0..length)
+                .map(|_| char::from(Alphanumeric.sample(rng)))
+                .collect()
+        });
         if fs::metadata(&filename).is_err() {
             return filename;
         }
     }
 }
+
+/// Generates a random value drawn from a normal (Gaussian) distribution.
+///
+/// Uses the Box–Muller transform: two independent uniform samples in `(0, 1]` are
+/// combined into one standard-normal sample, which is then scaled and shifted to the
+/// requested `mean` and `stddev`.
+///
+/// # Parameters
+/// - `mean`: The mean of the desired distribution.
+/// - `stddev`: The standard deviation of the desired distribution.
+///
+/// # Returns
+/// - A randomly generated `f64` drawn from `Normal(mean, stddev)`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let latency_ms = regd_testing::rand::generate_normal(50.0, 10.0);
+/// println!("Simulated latency: {} ms", latency_ms);
+/// ```
+pub fn generate_normal(mean: f64, stddev: f64) -> f64 {
+    let u1: f64 = 1.0 - generate_range(0.0..1.0);
+    let u2: f64 = 1.0 - generate_range(0.0..1.0);
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+    mean + stddev * z
+}
+
+/// Generates a random value drawn from an exponential distribution.
+///
+/// # Parameters
+/// - `lambda`: The rate parameter of the distribution. Must be positive.
+///
+/// # Returns
+/// - A randomly generated, non-negative `f64` drawn from `Exponential(lambda)`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let backoff_secs = regd_testing::rand::generate_exponential(0.5);
+/// println!("Simulated backoff: {} s", backoff_secs);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `lambda` is not positive.
+pub fn generate_exponential(lambda: f64) -> f64 {
+    assert!(lambda > 0.0, "lambda must be positive");
+    let u: f64 = generate_range(0.0..1.0);
+    -(1.0 - u).ln() / lambda
+}
+
+/// Generates a random boolean that is `true` with probability `p`.
+///
+/// # Parameters
+/// - `p`: The probability of returning `true`, in `[0.0, 1.0]`.
+///
+/// # Returns
+/// - `true` with probability `p`, `false` otherwise.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let should_fail = regd_testing::rand::generate_bool(0.1);
+/// println!("Simulated flaky branch: {}", should_fail);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `p` is not within `[0.0, 1.0]`.
+pub fn generate_bool(p: f64) -> bool {
+    assert!((0.0..=1.0).contains(&p), "p must be within [0.0, 1.0]");
+    generate_range(0.0..1.0) < p
+}
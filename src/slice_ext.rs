@@ -14,12 +14,17 @@
 
 //! This module contains a set of extensions of the existing Rust types.
 
+use std::collections::HashSet;
+
 /// A trait providing extension methods for slices.
 ///
 /// This trait adds several useful methods for working with slices. It provides:
 /// - [`choose`]: Randomly selects an element from the slice.
 /// - [`choose_mut`]: Randomly selects and mutably borrows an element from the slice.
+/// - [`choose_weighted`]: Randomly selects an element with probability proportional to its weight.
+/// - [`choose_weighted_mut`]: Mutable counterpart of [`choose_weighted`].
 /// - [`shuffle`]: Shuffles the slice in place.
+/// - [`sample`]: Samples multiple distinct elements from the slice without replacement.
 ///
 /// These methods operate on slices of any type `T` and assume that `T` is a type
 /// that can be accessed and modified within the slice.
@@ -40,11 +45,17 @@
 ///     *choice = 10;
 ///     println!("Modified choice: {}", choice);
 /// }
+///
+/// let picks = numbers.sample(2);
+/// assert_eq!(picks.len(), 2);
 /// ```
 ///
 /// [`choose`]: Self::choose
 /// [`choose_mut`]: Self::choose_mut
+/// [`choose_weighted`]: Self::choose_weighted
+/// [`choose_weighted_mut`]: Self::choose_weighted_mut
 /// [`shuffle`]: Self::shuffle
+/// [`sample`]: Self::sample
 pub trait SliceExt {
     /// The type of elements in the slice.
     type Item;
@@ -63,10 +74,106 @@ pub trait SliceExt {
     /// - `None` if the slice is empty.
     fn choose_mut(&mut self) -> Option<&mut Self::Item>;
 
+    /// Randomly selects an element from the slice with probability proportional to its weight.
+    ///
+    /// `weight` is called once per element to compute a non-negative weight. An element
+    /// with twice the weight of another is twice as likely to be selected.
+    ///
+    /// # Parameters
+    /// - `weight`: A function mapping each element to its (non-negative) selection weight.
+    ///
+    /// # Returns
+    /// - `Some(&Self::Item)` if the slice is non-empty and the weights sum to a positive total.
+    /// - `None` if the slice is empty, any weight is negative or non-finite, or the total weight is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use regd_testing::prelude::*;
+    /// use regd_testing::rand;
+    ///
+    /// let errors = ["io", "timeout", "ok"];
+    /// let weight = |e: &&str| if *e == "ok" { 1.0 } else { 10.0 };
+    ///
+    /// let first = rand::with_seed(7, || errors.choose_weighted(weight));
+    /// let second = rand::with_seed(7, || errors.choose_weighted(weight));
+    /// assert_eq!(first, second);
+    /// ```
+    fn choose_weighted<W, F>(&self, weight: F) -> Option<&Self::Item>
+    where
+        F: Fn(&Self::Item) -> W,
+        W: Into<f64>;
+
+    /// Mutable counterpart of [`choose_weighted`](Self::choose_weighted).
+    ///
+    /// # Parameters
+    /// - `weight`: A function mapping each element to its (non-negative) selection weight.
+    ///
+    /// # Returns
+    /// - `Some(&mut Self::Item)` if the slice is non-empty and the weights sum to a positive total.
+    /// - `None` if the slice is empty, any weight is negative or non-finite, or the total weight is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use regd_testing::prelude::*;
+    /// use regd_testing::rand;
+    ///
+    /// let mut first = [1, 2, 3];
+    /// let mut second = [1, 2, 3];
+    /// let weight = |v: &i32| *v as f64;
+    ///
+    /// rand::with_seed(11, || {
+    ///     if let Some(s) = first.choose_weighted_mut(weight) {
+    ///         *s += 100;
+    ///     }
+    /// });
+    /// rand::with_seed(11, || {
+    ///     if let Some(s) = second.choose_weighted_mut(weight) {
+    ///         *s += 100;
+    ///     }
+    /// });
+    /// assert_eq!(first, second);
+    /// ```
+    fn choose_weighted_mut<W, F>(&mut self, weight: F) -> Option<&mut Self::Item>
+    where
+        F: Fn(&Self::Item) -> W,
+        W: Into<f64>;
+
     /// Shuffles the elements of the slice in place.
     ///
     /// This method shuffles the slice, reordering its elements randomly.
     fn shuffle(&mut self);
+
+    /// Samples up to `k` distinct elements from the slice without replacement.
+    ///
+    /// Uses Floyd's algorithm, which draws only `k` indices, so its cost does not grow with
+    /// the slice length, unlike repeated [`choose`](Self::choose) or a full [`shuffle`](Self::shuffle):
+    /// `O(k)` space and `O(k log k)` time, the log factor coming from sorting the picked
+    /// indices into slice order.
+    ///
+    /// # Parameters
+    /// - `k`: The number of elements to sample. Clamped to the slice length.
+    ///
+    /// # Returns
+    /// - A `Vec` of up to `k` distinct element references, in slice order. Empty if `k == 0`
+    ///   or the slice is empty; the full slice (in order) if `k` is at least the slice length.
+    ///
+    /// # Examples
+    /// ```
+    /// use regd_testing::prelude::*;
+    /// use regd_testing::rand;
+    ///
+    /// let numbers = [1, 2, 3, 4, 5];
+    ///
+    /// let picks = rand::with_seed(3, || numbers.sample(3));
+    /// assert_eq!(picks.len(), 3);
+    ///
+    /// let again = rand::with_seed(3, || numbers.sample(3));
+    /// assert_eq!(picks, again);
+    ///
+    /// assert_eq!(numbers.sample(0).len(), 0);
+    /// assert_eq!(numbers.sample(10).len(), numbers.len());
+    /// ```
+    fn sample(&self, k: usize) -> Vec<&Self::Item>;
 }
 
 /// Generates a random index within the specified upper bound.
@@ -92,6 +199,28 @@ fn generate_index(sup: usize) -> usize {
     }
 }
 
+/// Builds the running-total prefix sums of `weight` applied to each element of `slice`.
+///
+/// Returns `None` if any weight is negative, infinite, or NaN, or if the total weight is
+/// zero, since none of those cases yield a valid selection.
+fn cumulative_weights<T, W, F>(slice: &[T], weight: F) -> Option<Vec<f64>>
+where
+    F: Fn(&T) -> W,
+    W: Into<f64>,
+{
+    let mut total = 0.0;
+    let mut sums = Vec::with_capacity(slice.len());
+    for item in slice {
+        let w = weight(item).into();
+        if !w.is_finite() || w < 0.0 {
+            return None;
+        }
+        total += w;
+        sums.push(total);
+    }
+    if total <= 0.0 { None } else { Some(sums) }
+}
+
 impl<T> SliceExt for [T] {
     type Item = T;
 
@@ -111,9 +240,48 @@ impl<T> SliceExt for [T] {
         }
     }
 
+    fn choose_weighted<W, F>(&self, weight: F) -> Option<&Self::Item>
+    where
+        F: Fn(&Self::Item) -> W,
+        W: Into<f64>,
+    {
+        let sums = cumulative_weights(self, weight)?;
+        let total = *sums.last().expect("non-empty after cumulative_weights");
+        let draw = crate::rand::generate_range(0.0..total);
+        let index = sums.partition_point(|&cumulative| cumulative <= draw);
+        Some(&self[index])
+    }
+
+    fn choose_weighted_mut<W, F>(&mut self, weight: F) -> Option<&mut Self::Item>
+    where
+        F: Fn(&Self::Item) -> W,
+        W: Into<f64>,
+    {
+        let sums = cumulative_weights(self, weight)?;
+        let total = *sums.last().expect("non-empty after cumulative_weights");
+        let draw = crate::rand::generate_range(0.0..total);
+        let index = sums.partition_point(|&cumulative| cumulative <= draw);
+        Some(&mut self[index])
+    }
+
     fn shuffle(&mut self) {
         for i in (1..self.len()).rev() {
             self.swap(i, generate_index(i + 1));
         }
     }
+
+    fn sample(&self, k: usize) -> Vec<&Self::Item> {
+        let n = self.len();
+        let k = k.min(n);
+        let mut picked = HashSet::with_capacity(k);
+        for j in (n - k)..n {
+            let t = generate_index(j + 1);
+            if !picked.insert(t) {
+                picked.insert(j);
+            }
+        }
+        let mut indices: Vec<usize> = picked.into_iter().collect();
+        indices.sort_unstable();
+        indices.into_iter().map(|i| &self[i]).collect()
+    }
 }